@@ -20,6 +20,8 @@
 //!
 //! Basic usage: Adapt an iterator to be sorted.
 //! ```
+//! # #[cfg(any(feature = "std", feature = "alloc"))]
+//! # {
 //! use window_sort_iterator::WindowSortIterExt;
 //!
 //! let a = &[4, 2, 3, 1];
@@ -29,10 +31,13 @@
 //! assert_eq!(Some(2), it.next());
 //! assert_eq!(Some(1), it.next());
 //! assert_eq!(None, it.next());
+//! # }
 //! ```
 //!
 //! Reverse, to use a min-heap:
 //! ```
+//! # #[cfg(any(feature = "std", feature = "alloc"))]
+//! # {
 //! use std::cmp::Reverse;
 //! use window_sort_iterator::window_sort;
 //!
@@ -43,12 +48,55 @@
 //! assert_eq!(Some(3), it.next());
 //! assert_eq!(Some(4), it.next());
 //! assert_eq!(None, it.next())
+//! # }
 //! ```
+//!
+//! If your item type isn't [Ord], or you want a different order than the one [Ord] gives you,
+//! use [window_sort_by] or [window_sort_by_key] instead of wrapping every item in [std::cmp::Reverse].
+//!
+//! [WindowSort], [WindowSortBy], [WindowMap] and [WindowSortChecked] buffer their window in a
+//! heap-allocated `BinaryHeap`/`Vec`/`VecDeque`, so they need either the `std` feature (on by
+//! default) or the allocator-only `alloc` feature. If you need to run without an allocator at
+//! all, e.g. on embedded targets, use [WindowSortN] / [window_sort_array] instead, with *no*
+//! features enabled: the window size is a const generic, so the window is buffered inline on the
+//! struct.
+//!
+//! If you want to look at each window instead of sorting it, use [window_map], which applies a
+//! function to every consecutive window of a fixed size, much like the unstable
+//! `Iterator::map_windows` in `std`.
+//!
+//! If you're not sure whether `window_size` is large enough to fully un-scramble your iterator,
+//! use [window_sort_checked]: it yields a `Result` for every item, with an `Err` whenever an item
+//! was emitted out of order.
+#![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+extern crate alloc;
+
+#[cfg(feature = "std")]
 use std::collections::BinaryHeap;
+#[cfg(feature = "std")]
+use std::collections::VecDeque;
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::collections::BinaryHeap;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::collections::VecDeque;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+use core::cmp::Ordering;
+use core::fmt;
+#[cfg(any(feature = "std", feature = "alloc"))]
+use core::iter::FusedIterator;
+use core::mem::MaybeUninit;
 
 /// An iterator adapter that sorts items within a sliding window.
 /// See the crate-level documentation for more info.
+#[cfg(any(feature = "std", feature = "alloc"))]
 pub struct WindowSort<I>
 where
     I: Iterator,
@@ -59,6 +107,7 @@ where
     heap: BinaryHeap<I::Item>,
 }
 
+#[cfg(any(feature = "std", feature = "alloc"))]
 impl<I> Iterator for WindowSort<I>
 where
     I: Iterator,
@@ -98,8 +147,42 @@ where
     }
 }
 
+// `WindowSort` only reorders items, it never adds or drops any: once the heap is empty and the
+// source is exhausted, it stays exhausted, provided the source itself is fused.
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<I> FusedIterator for WindowSort<I>
+where
+    I: FusedIterator,
+    I::Item: Ord,
+{
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<I> ExactSizeIterator for WindowSort<I>
+where
+    I: ExactSizeIterator,
+    I::Item: Ord,
+{
+    #[inline]
+    fn len(&self) -> usize {
+        self.heap.len() + self.orig.len()
+    }
+}
+
+// `DoubleEndedIterator` is intentionally not implemented: `self.heap` is a max-heap, which gives
+// no efficient access to its minimum (the item `next_back` would need to pop), only its maximum.
+// Finding the minimum would mean a linear scan per call, or replacing the heap with a
+// double-ended priority queue, neither of which is a "conditional" trait impl in the sense of the
+// `FusedIterator`/`ExactSizeIterator` impls above. It would also change what "the back" of a sliding window
+// even means: `next` pulls items from the front of `orig` into the heap and lets the window's
+// ordering pick the next item, whereas a sound `next_back` would need to pull from the back of
+// `orig` into that same heap and still agree with `next` on when both ends have met. That's a
+// different, heavier feature than the `FusedIterator`/`ExactSizeIterator` impls above, so it's
+// left out of scope here.
+
 /// Sorts the underlying iterator within a sliding window.
 /// See the crate-level documentation for more info.
+#[cfg(any(feature = "std", feature = "alloc"))]
 pub fn window_sort<I: Iterator>(xs: I, window_size: usize) -> WindowSort<I>
 where
     <I as Iterator>::Item: Ord,
@@ -111,30 +194,625 @@ where
     }
 }
 
+/// An iterator adapter that sorts items within a sliding window using a custom comparator.
+/// See the crate-level documentation for more info.
+///
+/// Unlike [WindowSort], this does not require `Item: Ord`, since [std::collections::BinaryHeap]
+/// can't be used with a comparator function. Instead, the window is kept in a [Vec] used as a
+/// binary max-heap, keyed by the comparator.
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub struct WindowSortBy<I, F>
+where
+    I: Iterator,
+{
+    orig: I,
+    window_size: usize,
+    heap: Vec<I::Item>,
+    cmp: F,
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<I, F> WindowSortBy<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item, &I::Item) -> Ordering,
+{
+    /// Pushes `item` onto the heap, then sifts it up until the heap property is restored.
+    fn push(&mut self, item: I::Item) {
+        self.heap.push(item);
+        let mut idx = self.heap.len() - 1;
+        while idx > 0 {
+            let parent = (idx - 1) / 2;
+            if (self.cmp)(&self.heap[idx], &self.heap[parent]) == Ordering::Greater {
+                self.heap.swap(idx, parent);
+                idx = parent;
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Pops the greatest item off the heap, then sifts the new root down until the heap property
+    /// is restored.
+    fn pop(&mut self) -> Option<I::Item> {
+        let last = self.heap.len().checked_sub(1)?;
+        self.heap.swap(0, last);
+        let item = self.heap.pop();
+
+        let len = self.heap.len();
+        let mut idx = 0;
+        loop {
+            let left = 2 * idx + 1;
+            let right = 2 * idx + 2;
+            let mut largest = idx;
+            if left < len && (self.cmp)(&self.heap[left], &self.heap[largest]) == Ordering::Greater
+            {
+                largest = left;
+            }
+            if right < len
+                && (self.cmp)(&self.heap[right], &self.heap[largest]) == Ordering::Greater
+            {
+                largest = right;
+            }
+            if largest == idx {
+                break;
+            }
+            self.heap.swap(idx, largest);
+            idx = largest;
+        }
+
+        item
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<I, F> Iterator for WindowSortBy<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item, &I::Item) -> Ordering,
+{
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        // Do we need to fill up the heap?
+        while self.heap.len() < self.window_size {
+            // Are there still items to be read from the underlying iterator?
+            if let Some(item) = self.orig.next() {
+                // If yes: push onto the heap.
+                self.push(item);
+            } else {
+                // If not: break from filling the heap, pop highest item.
+                break;
+            }
+        }
+
+        // Pop highest item off the heap.
+        // If the heap is empty this will return None.
+        self.pop()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let heap_items = self.heap.len();
+        match self.orig.size_hint() {
+            (lower, Some(upper)) => (
+                lower.saturating_add(heap_items),
+                Some(upper.saturating_add(heap_items)),
+            ),
+            (lower, None) => (lower.saturating_add(heap_items), None),
+        }
+    }
+}
+
+/// Sorts the underlying iterator within a sliding window, using `cmp` to compare items.
+/// See the crate-level documentation for more info.
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub fn window_sort_by<I, F>(xs: I, window_size: usize, cmp: F) -> WindowSortBy<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item, &I::Item) -> Ordering,
+{
+    WindowSortBy {
+        orig: xs,
+        window_size,
+        heap: Vec::new(),
+        cmp,
+    }
+}
+
+/// Sorts the underlying iterator within a sliding window, ordering items by the key returned by
+/// `key`.
+/// See the crate-level documentation for more info.
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub fn window_sort_by_key<I, K, F>(
+    xs: I,
+    window_size: usize,
+    mut key: F,
+) -> WindowSortBy<I, impl FnMut(&I::Item, &I::Item) -> Ordering>
+where
+    I: Iterator,
+    K: Ord,
+    F: FnMut(&I::Item) -> K,
+{
+    window_sort_by(xs, window_size, move |a, b| key(a).cmp(&key(b)))
+}
+
 /// Trait that extends iterators with functionality to sort items within a sliding window.
 /// See the crate-level documentation for more info.
 pub trait WindowSortIterExt: Sized {
+    #[cfg(any(feature = "std", feature = "alloc"))]
     fn window_sort(self, window_size: usize) -> WindowSort<Self>
     where
         Self: Iterator,
         <Self as Iterator>::Item: Ord;
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    fn window_sort_by<F>(self, window_size: usize, cmp: F) -> WindowSortBy<Self, F>
+    where
+        Self: Iterator,
+        F: FnMut(&<Self as Iterator>::Item, &<Self as Iterator>::Item) -> Ordering;
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    fn window_sort_by_key<K, F>(
+        self,
+        window_size: usize,
+        key: F,
+    ) -> WindowSortBy<Self, impl FnMut(&<Self as Iterator>::Item, &<Self as Iterator>::Item) -> Ordering>
+    where
+        Self: Iterator,
+        K: Ord,
+        F: FnMut(&<Self as Iterator>::Item) -> K;
+
+    fn window_sort_array<const N: usize>(self) -> WindowSortN<Self, N>
+    where
+        Self: Iterator,
+        <Self as Iterator>::Item: Ord;
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    fn window_sort_checked(self, window_size: usize) -> WindowSortChecked<Self>
+    where
+        Self: Iterator,
+        <Self as Iterator>::Item: Ord + Clone;
 }
 
-impl<I: Iterator> WindowSortIterExt for I
-where
-    <I as Iterator>::Item: Ord,
-{
+impl<I: Iterator> WindowSortIterExt for I {
     /// Sorts the underlying iterator within a sliding window.
     /// See the crate-level documentation for more info.
-    fn window_sort(self, window_size: usize) -> WindowSort<Self> {
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    fn window_sort(self, window_size: usize) -> WindowSort<Self>
+    where
+        <Self as Iterator>::Item: Ord,
+    {
         window_sort(self, window_size)
     }
+
+    /// Sorts the underlying iterator within a sliding window, using `cmp` to compare items.
+    /// See the crate-level documentation for more info.
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    fn window_sort_by<F>(self, window_size: usize, cmp: F) -> WindowSortBy<Self, F>
+    where
+        F: FnMut(&<Self as Iterator>::Item, &<Self as Iterator>::Item) -> Ordering,
+    {
+        window_sort_by(self, window_size, cmp)
+    }
+
+    /// Sorts the underlying iterator within a sliding window, ordering items by the key returned
+    /// by `key`.
+    /// See the crate-level documentation for more info.
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    fn window_sort_by_key<K, F>(
+        self,
+        window_size: usize,
+        key: F,
+    ) -> WindowSortBy<Self, impl FnMut(&<Self as Iterator>::Item, &<Self as Iterator>::Item) -> Ordering>
+    where
+        K: Ord,
+        F: FnMut(&<Self as Iterator>::Item) -> K,
+    {
+        window_sort_by_key(self, window_size, key)
+    }
+
+    /// Sorts the underlying iterator within a fixed-size `N` sliding window, without allocating.
+    /// See the crate-level documentation for more info.
+    fn window_sort_array<const N: usize>(self) -> WindowSortN<Self, N>
+    where
+        <Self as Iterator>::Item: Ord,
+    {
+        window_sort_array(self)
+    }
+
+    /// Sorts the underlying iterator within a sliding window, reporting items emitted out of
+    /// order because `window_size` was too small.
+    /// See the crate-level documentation for more info.
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    fn window_sort_checked(self, window_size: usize) -> WindowSortChecked<Self>
+    where
+        <Self as Iterator>::Item: Ord + Clone,
+    {
+        window_sort_checked(self, window_size)
+    }
+}
+
+/// An iterator adapter that sorts items within a fixed-size sliding window without allocating.
+/// See the crate-level documentation for more info.
+///
+/// The window size `N` is a const generic known at compile time, so the window is buffered
+/// inline in a `[MaybeUninit<I::Item>; N]` on the struct itself, rather than in a heap-allocated
+/// [std::collections::BinaryHeap] like [WindowSort] does. This makes `WindowSortN` usable under
+/// `#![no_std]` with only `core`.
+pub struct WindowSortN<I, const N: usize>
+where
+    I: Iterator,
+    I::Item: Ord,
+{
+    orig: I,
+    // Invariant: slots `0..len` are initialized, the rest are not.
+    heap: [MaybeUninit<I::Item>; N],
+    len: usize,
+}
+
+impl<I, const N: usize> WindowSortN<I, N>
+where
+    I: Iterator,
+    I::Item: Ord,
+{
+    #[inline]
+    fn get(&self, idx: usize) -> &I::Item {
+        // Safety: callers only ever pass indices below `self.len`, which are initialized.
+        unsafe { self.heap[idx].assume_init_ref() }
+    }
+
+    /// Pushes `item` into the heap, then sifts it up until the heap property is restored.
+    fn push(&mut self, item: I::Item) {
+        debug_assert!(self.len < N);
+        self.heap[self.len] = MaybeUninit::new(item);
+        let mut idx = self.len;
+        self.len += 1;
+
+        while idx > 0 {
+            let parent = (idx - 1) / 2;
+            if self.get(idx) > self.get(parent) {
+                self.heap.swap(idx, parent);
+                idx = parent;
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Pops the greatest item off the heap, then sifts the new root down until the heap property
+    /// is restored.
+    fn pop(&mut self) -> Option<I::Item> {
+        let last = self.len.checked_sub(1)?;
+        self.heap.swap(0, last);
+        self.len -= 1;
+
+        // Safety: slot `self.len` held the popped item and was just swapped into place; it is
+        // still initialized, and is no longer part of the logical window.
+        let item = unsafe { self.heap[self.len].assume_init_read() };
+
+        let len = self.len;
+        let mut idx = 0;
+        loop {
+            let left = 2 * idx + 1;
+            let right = 2 * idx + 2;
+            let mut largest = idx;
+            if left < len && self.get(left) > self.get(largest) {
+                largest = left;
+            }
+            if right < len && self.get(right) > self.get(largest) {
+                largest = right;
+            }
+            if largest == idx {
+                break;
+            }
+            self.heap.swap(idx, largest);
+            idx = largest;
+        }
+
+        Some(item)
+    }
+}
+
+impl<I, const N: usize> Drop for WindowSortN<I, N>
+where
+    I: Iterator,
+    I::Item: Ord,
+{
+    fn drop(&mut self) {
+        for slot in &mut self.heap[..self.len] {
+            // Safety: slots `0..len` are always initialized.
+            unsafe { slot.assume_init_drop() };
+        }
+    }
+}
+
+impl<I, const N: usize> Iterator for WindowSortN<I, N>
+where
+    I: Iterator,
+    I::Item: Ord,
+{
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        // Do we need to fill up the heap?
+        while self.len < N {
+            // Are there still items to be read from the underlying iterator?
+            if let Some(item) = self.orig.next() {
+                // If yes: push onto the heap.
+                self.push(item);
+            } else {
+                // If not: break from filling the heap, pop highest item.
+                break;
+            }
+        }
+
+        // Pop highest item off the heap.
+        // If the heap is empty this will return None.
+        self.pop()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let heap_items = self.len;
+        match self.orig.size_hint() {
+            (lower, Some(upper)) => (
+                lower.saturating_add(heap_items),
+                Some(upper.saturating_add(heap_items)),
+            ),
+            (lower, None) => (lower.saturating_add(heap_items), None),
+        }
+    }
+}
+
+/// Sorts the underlying iterator within a fixed-size `N` sliding window, without allocating.
+/// See the crate-level documentation for more info.
+pub fn window_sort_array<I, const N: usize>(xs: I) -> WindowSortN<I, N>
+where
+    I: Iterator,
+    I::Item: Ord,
+{
+    WindowSortN {
+        orig: xs,
+        // Safety: an array of `MaybeUninit<T>` does not require initialization itself.
+        heap: unsafe { MaybeUninit::uninit().assume_init() },
+        len: 0,
+    }
+}
+
+/// An iterator adapter that applies a function to each consecutive, fixed-size window of the
+/// underlying iterator.
+/// See the crate-level documentation for more info.
+///
+/// Unlike [WindowSort], this does not reorder items: it buffers `window_size` items in a
+/// [VecDeque], calls `f` on the window once it is full, then drops the oldest item so the window
+/// slides by one for the next call. The underlying iterator is only ever advanced when this
+/// iterator is, so windows are produced lazily.
+///
+/// `window_size` is a runtime value rather than a const generic: this mirrors [WindowSort] /
+/// [window_sort], the crate's primary adapter, rather than the const-generic, allocation-free
+/// [WindowSortN]. If you need a `window_map` that never allocates, buffer into a fixed-size array
+/// yourself the way [WindowSortN] does internally.
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub struct WindowMap<I, F>
+where
+    I: Iterator,
+{
+    orig: I,
+    window_size: usize,
+    buf: VecDeque<I::Item>,
+    f: F,
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<I, F, R> Iterator for WindowMap<I, F>
+where
+    I: Iterator,
+    F: FnMut(&[I::Item]) -> R,
+{
+    type Item = R;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        // A zero-size window can never be filled, so there's nothing to call `f` on.
+        if self.window_size == 0 {
+            return None;
+        }
+
+        // Fill up the window.
+        while self.buf.len() < self.window_size {
+            match self.orig.next() {
+                Some(item) => self.buf.push_back(item),
+                // The underlying iterator ended before the window filled up: we'll never be
+                // able to produce a full window again.
+                None => return None,
+            }
+        }
+
+        // The window is full: apply `f` to it, then slide the window by dropping the oldest item.
+        let result = (self.f)(self.buf.make_contiguous());
+        self.buf.pop_front();
+
+        Some(result)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // A zero-size window can never be filled, so `next` always returns `None` immediately.
+        if self.window_size == 0 {
+            return (0, Some(0));
+        }
+
+        let buffered = self.buf.len();
+        let window_size = self.window_size;
+        let map = move |remaining: usize| {
+            buffered
+                .saturating_add(remaining)
+                .saturating_sub(window_size.saturating_sub(1))
+        };
+
+        match self.orig.size_hint() {
+            (lower, Some(upper)) => (map(lower), Some(map(upper))),
+            (lower, None) => (map(lower), None),
+        }
+    }
+}
+
+/// Applies `f` to each consecutive window of `window_size` items of the underlying iterator.
+/// See the crate-level documentation for more info.
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub fn window_map<I, F, R>(xs: I, window_size: usize, f: F) -> WindowMap<I, F>
+where
+    I: Iterator,
+    F: FnMut(&[I::Item]) -> R,
+{
+    WindowMap {
+        orig: xs,
+        window_size,
+        buf: VecDeque::with_capacity(window_size),
+        f,
+    }
+}
+
+/// Trait that extends iterators with functionality to apply a function to each consecutive
+/// sliding window.
+/// See the crate-level documentation for more info.
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub trait WindowMapIterExt: Sized {
+    fn window_map<F, R>(self, window_size: usize, f: F) -> WindowMap<Self, F>
+    where
+        Self: Iterator,
+        F: FnMut(&[<Self as Iterator>::Item]) -> R;
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<I: Iterator> WindowMapIterExt for I {
+    /// Applies `f` to each consecutive window of `window_size` items of the underlying iterator.
+    /// See the crate-level documentation for more info.
+    fn window_map<F, R>(self, window_size: usize, f: F) -> WindowMap<Self, F>
+    where
+        F: FnMut(&[<Self as Iterator>::Item]) -> R,
+    {
+        window_map(self, window_size, f)
+    }
+}
+
+/// Error returned by [WindowSortChecked] / [window_sort_checked] when `window_size` was too
+/// small to fully un-scramble the underlying iterator: an item was emitted even though it should
+/// have come before the previously emitted item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutOfWindowError<T> {
+    /// The item that was emitted right before `item`.
+    pub previous: T,
+    /// The item that broke the sort order: [WindowSortChecked] emits items in non-increasing
+    /// order, but `item` compared greater than `previous`.
+    pub item: T,
+}
+
+impl<T: fmt::Debug> fmt::Display for OutOfWindowError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "window_size too small: {:?} was emitted after {:?}, but compares greater",
+            self.item, self.previous
+        )
+    }
+}
+
+impl<T: fmt::Debug> core::error::Error for OutOfWindowError<T> {}
+
+/// An iterator adapter that sorts items within a sliding window, like [WindowSort], but reports
+/// when `window_size` was too small to fully sort the underlying iterator.
+/// See the crate-level documentation for more info.
+///
+/// This yields `Result<I::Item, OutOfWindowError<I::Item>>`: the last emitted item is retained,
+/// and each newly popped item is compared against it before being emitted. If the new item
+/// compares greater than the last one — i.e. it should have been emitted earlier, but arrived
+/// too late to catch up with a window this small — an `Err` is yielded instead of silently
+/// producing unsorted output. The iterator keeps going after an `Err`, so it reports every
+/// inversion rather than stopping at the first one.
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub struct WindowSortChecked<I>
+where
+    I: Iterator,
+    I::Item: Ord + Clone,
+{
+    orig: I,
+    window_size: usize,
+    heap: BinaryHeap<I::Item>,
+    last: Option<I::Item>,
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<I> Iterator for WindowSortChecked<I>
+where
+    I: Iterator,
+    I::Item: Ord + Clone,
+{
+    type Item = Result<I::Item, OutOfWindowError<I::Item>>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        // Do we need to fill up the heap?
+        while self.heap.len() < self.window_size {
+            // Are there still items to be read from the underlying iterator?
+            if let Some(item) = self.orig.next() {
+                // If yes: push onto the heap.
+                self.heap.push(item);
+            } else {
+                // If not: break from filling the heap, pop highest item.
+                break;
+            }
+        }
+
+        // Pop highest item off the heap.
+        // If the heap is empty this will return None.
+        let item = self.heap.pop()?;
+        let previous = self.last.replace(item.clone());
+
+        Some(match previous {
+            Some(previous) if item > previous => Err(OutOfWindowError { previous, item }),
+            _ => Ok(item),
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let heap_items = self.heap.len();
+        match self.orig.size_hint() {
+            (lower, Some(upper)) => (
+                lower.saturating_add(heap_items),
+                Some(upper.saturating_add(heap_items)),
+            ),
+            (lower, None) => (lower.saturating_add(heap_items), None),
+        }
+    }
+}
+
+/// Sorts the underlying iterator within a sliding window, reporting items emitted out of order
+/// because `window_size` was too small.
+/// See the crate-level documentation for more info.
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub fn window_sort_checked<I>(xs: I, window_size: usize) -> WindowSortChecked<I>
+where
+    I: Iterator,
+    I::Item: Ord + Clone,
+{
+    WindowSortChecked {
+        orig: xs,
+        window_size,
+        heap: BinaryHeap::new(),
+        last: None,
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[cfg(any(feature = "std", feature = "alloc"))]
     #[test]
     fn should_sort_i32_fn() {
         let a = &[3_i32, 4, 2, 1];
@@ -146,6 +824,7 @@ mod tests {
         assert_eq!(None, it.next());
     }
 
+    #[cfg(any(feature = "std", feature = "alloc"))]
     #[test]
     fn should_sort_i32_method() {
         let a = &[3_i32, 4, 2, 1];
@@ -157,6 +836,7 @@ mod tests {
         assert_eq!(None, it.next());
     }
 
+    #[cfg(any(feature = "std", feature = "alloc"))]
     #[test]
     fn should_sort_window_only() {
         let a = &[4_i32, 2, 1, 3];
@@ -168,6 +848,7 @@ mod tests {
         assert_eq!(None, it.next());
     }
 
+    #[cfg(any(feature = "std", feature = "alloc"))]
     #[test]
     fn small_underlying_iterator() {
         let a = &[2_i32, 3, 4, 1];
@@ -178,4 +859,309 @@ mod tests {
         assert_eq!(Some(1), it.next());
         assert_eq!(None, it.next());
     }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn should_be_fused() {
+        let a = &[3_i32, 4, 2, 1];
+        let mut it = window_sort(a.iter().cloned(), 2);
+        assert_eq!(Some(4), it.next());
+        assert_eq!(Some(3), it.next());
+        assert_eq!(Some(2), it.next());
+        assert_eq!(Some(1), it.next());
+        assert_eq!(None, it.next());
+        assert_eq!(None, it.next());
+        assert_eq!(None, it.next());
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn should_report_exact_len() {
+        let a = &[3_i32, 4, 2, 1];
+        let mut it = window_sort(a.iter().cloned(), 2);
+        assert_eq!(4, it.len());
+        it.next();
+        assert_eq!(3, it.len());
+        it.next();
+        assert_eq!(2, it.len());
+        it.next();
+        assert_eq!(1, it.len());
+        it.next();
+        assert_eq!(0, it.len());
+        assert_eq!(None, it.next());
+        assert_eq!(0, it.len());
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn should_sort_by_reverse_fn() {
+        let a = &[3_i32, 4, 2, 1];
+        let mut it = window_sort_by(a.iter().cloned(), 2, |a: &i32, b: &i32| b.cmp(a));
+        assert_eq!(Some(3), it.next());
+        assert_eq!(Some(2), it.next());
+        assert_eq!(Some(1), it.next());
+        assert_eq!(Some(4), it.next());
+        assert_eq!(None, it.next());
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn should_sort_by_reverse_method() {
+        let a = &[3_i32, 4, 2, 1];
+        let mut it = a
+            .iter()
+            .cloned()
+            .window_sort_by(2, |a: &i32, b: &i32| b.cmp(a));
+        assert_eq!(Some(3), it.next());
+        assert_eq!(Some(2), it.next());
+        assert_eq!(Some(1), it.next());
+        assert_eq!(Some(4), it.next());
+        assert_eq!(None, it.next());
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn should_sort_by_key_fn() {
+        let a = &[(3_i32, "c"), (4, "d"), (2, "b"), (1, "a")];
+        let mut it = window_sort_by_key(a.iter().cloned(), 2, |item| item.0);
+        assert_eq!(Some((4, "d")), it.next());
+        assert_eq!(Some((3, "c")), it.next());
+        assert_eq!(Some((2, "b")), it.next());
+        assert_eq!(Some((1, "a")), it.next());
+        assert_eq!(None, it.next());
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn should_sort_by_key_method() {
+        let a = &[(3_i32, "c"), (4, "d"), (2, "b"), (1, "a")];
+        let mut it = a.iter().cloned().window_sort_by_key(2, |item| item.0);
+        assert_eq!(Some((4, "d")), it.next());
+        assert_eq!(Some((3, "c")), it.next());
+        assert_eq!(Some((2, "b")), it.next());
+        assert_eq!(Some((1, "a")), it.next());
+        assert_eq!(None, it.next());
+    }
+
+    #[test]
+    fn should_sort_array_fn() {
+        let a = &[3_i32, 4, 2, 1];
+        let mut it = window_sort_array::<_, 2>(a.iter().cloned());
+        assert_eq!(Some(4), it.next());
+        assert_eq!(Some(3), it.next());
+        assert_eq!(Some(2), it.next());
+        assert_eq!(Some(1), it.next());
+        assert_eq!(None, it.next());
+    }
+
+    #[test]
+    fn should_sort_array_method() {
+        let a = &[3_i32, 4, 2, 1];
+        let mut it = a.iter().cloned().window_sort_array::<2>();
+        assert_eq!(Some(4), it.next());
+        assert_eq!(Some(3), it.next());
+        assert_eq!(Some(2), it.next());
+        assert_eq!(Some(1), it.next());
+        assert_eq!(None, it.next());
+    }
+
+    #[test]
+    fn small_underlying_iterator_array() {
+        let a = &[2_i32, 3, 4, 1];
+        let mut it = window_sort_array::<_, 10>(a.iter().cloned());
+        assert_eq!(Some(4), it.next());
+        assert_eq!(Some(3), it.next());
+        assert_eq!(Some(2), it.next());
+        assert_eq!(Some(1), it.next());
+        assert_eq!(None, it.next());
+    }
+
+    // Relies on `std::rc::Rc`/`std::cell::Cell` purely as a test convenience for counting drops;
+    // not available without the `std` feature.
+    #[cfg(feature = "std")]
+    #[test]
+    fn drops_buffered_items_array() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        struct DropCounter(i32, #[allow(dead_code)] Rc<Cell<usize>>);
+
+        impl Drop for DropCounter {
+            fn drop(&mut self) {
+                self.1.set(self.1.get() + 1);
+            }
+        }
+
+        impl PartialEq for DropCounter {
+            fn eq(&self, other: &Self) -> bool {
+                self.0 == other.0
+            }
+        }
+        impl Eq for DropCounter {}
+        impl PartialOrd for DropCounter {
+            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+        impl Ord for DropCounter {
+            fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+                self.0.cmp(&other.0)
+            }
+        }
+
+        let dropped = Rc::new(Cell::new(0));
+        let a = vec![
+            DropCounter(3, dropped.clone()),
+            DropCounter(4, dropped.clone()),
+            DropCounter(2, dropped.clone()),
+        ];
+
+        let mut it = window_sort_array::<_, 2>(a.into_iter());
+        it.next();
+        // Drop the iterator while it still has buffered items; they must be dropped too, and not
+        // double-dropped.
+        drop(it);
+        assert_eq!(3, dropped.get());
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn should_map_windows_fn() {
+        let a = &[1_i32, 2, 3, 4];
+        let mut it = window_map(a.iter().cloned(), 2, |w: &[i32]| w.iter().sum::<i32>());
+        assert_eq!(Some(3), it.next());
+        assert_eq!(Some(5), it.next());
+        assert_eq!(Some(7), it.next());
+        assert_eq!(None, it.next());
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn should_map_windows_method() {
+        let a = &[1_i32, 2, 3, 4];
+        let mut it = a
+            .iter()
+            .cloned()
+            .window_map(2, |w: &[i32]| w.iter().sum::<i32>());
+        assert_eq!(Some(3), it.next());
+        assert_eq!(Some(5), it.next());
+        assert_eq!(Some(7), it.next());
+        assert_eq!(None, it.next());
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn small_underlying_iterator_map() {
+        let a = &[1_i32, 2];
+        let mut it = window_map(a.iter().cloned(), 10, |w: &[i32]| w.iter().sum::<i32>());
+        assert_eq!(None, it.next());
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn zero_size_window_map_terminates() {
+        let a = &[1_i32, 2, 3];
+        let mut it = window_map(a.iter().cloned(), 0, |w: &[i32]| w.iter().sum::<i32>());
+        assert_eq!((0, Some(0)), it.size_hint());
+        assert_eq!(None, it.next());
+        assert_eq!(None, it.next());
+    }
+
+    // Relies on `std::cell::Cell` purely as a test convenience for counting pulls; not available
+    // without the `std` feature.
+    #[cfg(feature = "std")]
+    #[test]
+    fn window_map_is_lazy() {
+        use std::cell::Cell;
+
+        let pulled = Cell::new(0);
+        let a = [1_i32, 2, 3, 4];
+        let mut it = a
+            .iter()
+            .cloned()
+            .inspect(|_| pulled.set(pulled.get() + 1))
+            .window_map(2, |w: &[i32]| w.iter().sum::<i32>());
+
+        assert_eq!(0, pulled.get());
+        assert_eq!(Some(3), it.next());
+        assert_eq!(2, pulled.get());
+        assert_eq!(Some(5), it.next());
+        assert_eq!(3, pulled.get());
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn should_sort_checked_fn() {
+        let a = &[3_i32, 4, 2, 1];
+        let mut it = window_sort_checked(a.iter().cloned(), 2);
+        assert_eq!(Some(Ok(4)), it.next());
+        assert_eq!(Some(Ok(3)), it.next());
+        assert_eq!(Some(Ok(2)), it.next());
+        assert_eq!(Some(Ok(1)), it.next());
+        assert_eq!(None, it.next());
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn should_sort_checked_method() {
+        let a = &[3_i32, 4, 2, 1];
+        let mut it = a.iter().cloned().window_sort_checked(2);
+        assert_eq!(Some(Ok(4)), it.next());
+        assert_eq!(Some(Ok(3)), it.next());
+        assert_eq!(Some(Ok(2)), it.next());
+        assert_eq!(Some(Ok(1)), it.next());
+        assert_eq!(None, it.next());
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn window_sort_checked_reports_out_of_window_items() {
+        // `4` arrives too late to catch up with `3` under a window of size 2: `3` is emitted
+        // first, then `4`, which is an inversion.
+        let a = &[3_i32, 1, 4, 2];
+        let mut it = window_sort_checked(a.iter().cloned(), 2);
+        assert_eq!(Some(Ok(3)), it.next());
+        assert_eq!(
+            Some(Err(OutOfWindowError {
+                previous: 3,
+                item: 4
+            })),
+            it.next()
+        );
+        assert_eq!(Some(Ok(2)), it.next());
+        assert_eq!(Some(Ok(1)), it.next());
+        assert_eq!(None, it.next());
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn window_sort_checked_keeps_going_after_an_error() {
+        // Every item after the first is greater than everything emitted so far, so every
+        // emission after the first is an inversion. The iterator must report all of them.
+        let a = &[1_i32, 2, 3, 4];
+        let mut it = window_sort_checked(a.iter().cloned(), 1);
+        assert_eq!(Some(Ok(1)), it.next());
+        assert_eq!(
+            Some(Err(OutOfWindowError {
+                previous: 1,
+                item: 2
+            })),
+            it.next()
+        );
+        assert_eq!(
+            Some(Err(OutOfWindowError {
+                previous: 2,
+                item: 3
+            })),
+            it.next()
+        );
+        assert_eq!(
+            Some(Err(OutOfWindowError {
+                previous: 3,
+                item: 4
+            })),
+            it.next()
+        );
+        assert_eq!(None, it.next());
+    }
 }